@@ -87,52 +87,30 @@ impl Table {
     where
         F: FnMut(&Table, &Vec<&'t str>, bool) -> Result,
     {
-        let mut positions = vec![];
-        self.visit_nested_tables(path, is_array_of_tables, &mut |t, _, _| {
+        // A single DFS collects every table along with a sort key, then one
+        // stable sort puts them in "original" order. A table with a
+        // `position` sorts by that position; a table without one (added
+        // programmatically) inherits the position of the most recently
+        // visited positioned table, so it stays adjacent to the table it was
+        // inserted next to. Ties (several `None`-positioned tables sharing an
+        // anchor, or genuine duplicate positions) are broken by DFS
+        // visitation order, which the stable sort then preserves.
+        let mut entries: Vec<(usize, usize, Vec<&'t str>, bool, &'t Table)> = Vec::new();
+        let mut anchor_position = 0;
+        let mut visitation_index = 0;
+        self.visit_nested_tables(path, is_array_of_tables, &mut |t, path, is_array| {
             if let Some(pos) = t.position {
-                positions.push(pos);
+                anchor_position = pos;
             }
+            entries.push((anchor_position, visitation_index, path.clone(), is_array, t));
+            visitation_index += 1;
             Ok(())
         })?;
-        positions.sort();
-        let mut position_iter = positions.iter();
-
-        // If a table has a .position set then we calculate whether we should
-        // print based on whether it matches current_position. If .position is
-        // None then it was added programatically and we decide whether to print
-        // it based on whether we printed the previous table we visited. This
-        // is to avoid printing tables more than once.
-        //
-        // We set should_print to true the first time we went around the
-        // loop, so initially None-positioned Tables will have already been
-        // printed.
-        let mut should_print = true;
-
-        let mut current_position: Option<&usize> = position_iter.next();
-        while current_position.is_some() {
-            self.visit_nested_tables(path, is_array_of_tables, &mut |t, path, is_array| {
-                if current_position.is_none() && !should_print {
-                    return Ok(());
-                }
-                match &t.position {
-                    Some(_) => {
-                        if t.position.as_ref() == current_position {
-                            current_position = position_iter.next();
-                            should_print = true;
-                        } else {
-                            should_print = false;
-                        }
-                    }
-                    // This table doesn't have a position, so only print it if
-                    // should_print is still set from the previous table.
-                    None => (),
-                }
-                if should_print {
-                    callback(t, path, is_array)?
-                }
-                Ok(())
-            })?;
-            should_print = false;
+
+        entries.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+
+        for (_, _, path, is_array, t) in &entries {
+            callback(t, path, *is_array)?;
         }
         Ok(())
     }
@@ -169,17 +147,33 @@ impl Table {
     }
 }
 
+/// Whether a table's header (`[path]` or `[[path]]`) should be emitted.
+///
+/// Array-of-tables entries always get a header, even when the table body is
+/// empty, so structurally-significant elements like a bare `[[x]]` survive a
+/// parse/serialize round-trip — `visit_table` already special-cased this
+/// before `write_value_canonical` and `write_value_with` existed. This
+/// function just gives the three renderers one shared copy of that rule
+/// instead of three duplicated `if`/`else if` chains. A plain nested table
+/// only gets skipped when it's `implicit` (created solely to hold a dotted
+/// key, e.g. the `a` in `[a.b]`) and has no values of its own.
+fn should_print_table_header(table: &Table, is_array_of_tables: bool) -> bool {
+    is_array_of_tables || !(table.implicit && table.values_len() == 0)
+}
+
 fn visit_table(f: &mut Write, table: &Table, path: &[&str], is_array_of_tables: bool) -> Result {
     if path.is_empty() {
         // don't print header for the root node
-    } else if is_array_of_tables {
-        write!(f, "{}[[", table.decor.prefix)?;
-        write!(f, "{}", path.join("."))?;
-        writeln!(f, "]]{}", table.decor.suffix)?;
-    } else if !(table.implicit && table.values_len() == 0) {
-        write!(f, "{}[", table.decor.prefix)?;
-        write!(f, "{}", path.join("."))?;
-        writeln!(f, "]{}", table.decor.suffix)?;
+    } else if should_print_table_header(table, is_array_of_tables) {
+        if is_array_of_tables {
+            write!(f, "{}[[", table.decor.prefix)?;
+            write!(f, "{}", path.join("."))?;
+            writeln!(f, "]]{}", table.decor.suffix)?;
+        } else {
+            write!(f, "{}[", table.decor.prefix)?;
+            write!(f, "{}", path.join("."))?;
+            writeln!(f, "]{}", table.decor.suffix)?;
+        }
     }
     // print table body
     for kv in table.items.values() {
@@ -201,14 +195,365 @@ impl Display for Table {
     }
 }
 
+impl Value {
+    /// Returns a canonical string representation of this value.
+    ///
+    /// Unlike [`Display`], this ignores any preserved raw representation and
+    /// re-renders the value from its parsed data: strings are fully escaped
+    /// and integral floats are suffixed with `.0` so they round-trip as
+    /// floats rather than integers. This guarantees well-formed output even
+    /// if the value was constructed or mutated programmatically.
+    pub fn to_string_canonical(&self) -> String {
+        let mut string = String::new();
+        write_value_canonical(&mut string, self).unwrap();
+        string
+    }
+}
+
+fn write_value_canonical(f: &mut dyn Write, value: &Value) -> Result {
+    match *value {
+        Value::Integer(ref repr) => {
+            write!(f, "{}{}{}", repr.repr.decor.prefix, repr.value, repr.repr.decor.suffix)
+        }
+        Value::Boolean(ref repr) => {
+            write!(f, "{}{}{}", repr.repr.decor.prefix, repr.value, repr.repr.decor.suffix)
+        }
+        Value::Float(ref repr) => write!(
+            f,
+            "{}{}{}",
+            repr.repr.decor.prefix,
+            format_float_canonical(repr.value),
+            repr.repr.decor.suffix
+        ),
+        Value::String(ref repr) => write!(
+            f,
+            "{}{}{}",
+            repr.repr.decor.prefix,
+            escape_string_canonical(&repr.value),
+            repr.repr.decor.suffix
+        ),
+        Value::DateTime(ref repr) => {
+            write!(f, "{}{}{}", repr.repr.decor.prefix, repr.value, repr.repr.decor.suffix)
+        }
+        Value::Array(ref array) => write_array_canonical(f, array),
+        Value::InlineTable(ref table) => write_inline_table_canonical(f, table),
+    }
+}
+
+fn write_array_canonical(f: &mut dyn Write, array: &Array) -> Result {
+    write!(f, "{}[", array.decor.prefix)?;
+    for (i, v) in array.iter().enumerate() {
+        if i > 0 {
+            write!(f, ",")?;
+        }
+        write_value_canonical(f, v)?;
+    }
+    if array.trailing_comma {
+        write!(f, ",")?;
+    }
+    write!(f, "{}", array.trailing)?;
+    write!(f, "]{}", array.decor.suffix)
+}
+
+fn write_inline_table_canonical(f: &mut dyn Write, table: &InlineTable) -> Result {
+    write!(f, "{}{{", table.decor.prefix)?;
+    write!(f, "{}", table.preamble)?;
+    for (i, (key, value)) in table
+        .items
+        .iter()
+        .filter(|&(_, kv)| kv.value.is_value())
+        .map(|(_, kv)| (&kv.key, kv.value.as_value().unwrap()))
+        .enumerate()
+    {
+        if i > 0 {
+            write!(f, ",")?;
+        }
+        write!(f, "{}=", key)?;
+        write_value_canonical(f, value)?;
+    }
+    write!(f, "}}{}", table.decor.suffix)
+}
+
+/// Escapes a string as a canonical TOML basic string, including the
+/// surrounding quotes.
+pub(crate) fn escape_string_canonical(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '\u{8}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{c}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 || c as u32 == 0x7f => {
+                // Unwrap is safe; writing to a String never fails.
+                write!(out, "\\u{:04x}", c as u32).unwrap();
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Formats a float the way canonical TOML expects: integral values gain a
+/// `.0` suffix so they keep round-tripping as floats instead of integers.
+pub(crate) fn format_float_canonical(v: f64) -> String {
+    if v.is_nan() {
+        if v.is_sign_negative() {
+            "-nan".to_string()
+        } else {
+            "nan".to_string()
+        }
+    } else if v.is_infinite() {
+        if v.is_sign_negative() {
+            "-inf".to_string()
+        } else {
+            "inf".to_string()
+        }
+    } else if v.fract() == 0.0 {
+        format!("{:.1}", v)
+    } else {
+        format!("{}", v)
+    }
+}
+
+fn visit_table_canonical(
+    f: &mut dyn Write,
+    table: &Table,
+    path: &[&str],
+    is_array_of_tables: bool,
+) -> Result {
+    if path.is_empty() {
+        // don't print header for the root node
+    } else if should_print_table_header(table, is_array_of_tables) {
+        if is_array_of_tables {
+            write!(f, "{}[[", table.decor.prefix)?;
+            write!(f, "{}", path.join("."))?;
+            writeln!(f, "]]{}", table.decor.suffix)?;
+        } else {
+            write!(f, "{}[", table.decor.prefix)?;
+            write!(f, "{}", path.join("."))?;
+            writeln!(f, "]{}", table.decor.suffix)?;
+        }
+    }
+    // print table body
+    for kv in table.items.values() {
+        if let Item::Value(ref value) = kv.value {
+            write!(f, "{}=", kv.key)?;
+            write_value_canonical(f, value)?;
+            writeln!(f)?;
+        }
+    }
+    Ok(())
+}
+
+impl Document {
+    /// Returns a canonical string representation of the TOML document.
+    ///
+    /// Unlike [`Document::to_string`](Display::fmt), every scalar is
+    /// re-rendered from its parsed value instead of its preserved raw text,
+    /// so a document that was built or mutated programmatically (and may
+    /// therefore contain an invalid preserved representation) always
+    /// serializes to well-formed TOML.
+    pub fn to_string_canonical(&self) -> String {
+        let mut string = String::default();
+        let mut path = Vec::new();
+
+        self.as_table()
+            .visit_nested_tables(&mut path, false, &mut |t, path, is_array| {
+                visit_table_canonical(&mut string, t, path, is_array)
+            })
+            // write! to string always succeeds, unless we are out of memory,
+            // in which case we can't do much about it.
+            .unwrap();
+
+        string.push_str(&self.trailing);
+        string
+    }
+}
+
+/// Trailing-comma policy for arrays that get wrapped onto multiple lines by
+/// [`FormatOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingComma {
+    /// Keep whatever trailing comma the source already had.
+    Preserve,
+    /// Always add a trailing comma after the last element.
+    Always,
+    /// Never add a trailing comma after the last element.
+    Never,
+}
+
+/// Formatting style options for [`Document::to_string_with`].
+///
+/// The defaults reproduce the existing format-preserving [`Display`] output:
+/// `to_string_with(&FormatOptions::default())` returns the same string as
+/// `to_string()`. Values, including the elements of a wrapped array, are
+/// written with their preserved decor, so whitespace kept from the source is
+/// not normalized and comes in addition to the spacing these options add.
+/// Arrays are left on a single line unless a wrap threshold or column budget
+/// is set.
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// Number of spaces used for each level of indentation in a wrapped
+    /// array.
+    pub indent_width: usize,
+    /// Whether to surround `=` with a single space on each side.
+    pub space_around_eq: bool,
+    /// Trailing-comma policy for wrapped arrays.
+    ///
+    /// This only applies once an array has actually been wrapped onto
+    /// multiple lines (see `array_wrap_threshold` and
+    /// `array_wrap_column_budget`). An array short enough to stay on one
+    /// line keeps its original preserved trailing comma regardless of this
+    /// setting — `TrailingComma::Always` will not add a comma to a
+    /// single-line `[1, 2, 3]`.
+    pub trailing_comma: TrailingComma,
+    /// Wrap an array onto multiple indented lines once it has more than this
+    /// many elements. `None` disables wrapping by element count.
+    pub array_wrap_threshold: Option<usize>,
+    /// Wrap an array onto multiple indented lines once its single-line
+    /// rendering would exceed this many columns. `None` disables wrapping by
+    /// column budget.
+    pub array_wrap_column_budget: Option<usize>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            indent_width: 4,
+            space_around_eq: false,
+            trailing_comma: TrailingComma::Preserve,
+            array_wrap_threshold: None,
+            array_wrap_column_budget: None,
+        }
+    }
+}
+
+fn should_wrap_array(array: &Array, inline: &str, options: &FormatOptions) -> bool {
+    if let Some(threshold) = options.array_wrap_threshold {
+        if array.iter().count() > threshold {
+            return true;
+        }
+    }
+    if let Some(budget) = options.array_wrap_column_budget {
+        if inline.chars().count() > budget {
+            return true;
+        }
+    }
+    false
+}
+
+fn write_array_with(
+    f: &mut dyn Write,
+    array: &Array,
+    options: &FormatOptions,
+    indent_level: usize,
+) -> Result {
+    let inline = array.to_string();
+    if !should_wrap_array(array, &inline, options) {
+        return write!(f, "{}", inline);
+    }
+
+    let inner_indent = " ".repeat(options.indent_width * (indent_level + 1));
+    let outer_indent = " ".repeat(options.indent_width * indent_level);
+    let elements: Vec<_> = array.iter().collect();
+
+    writeln!(f, "{}[", array.decor.prefix)?;
+    for (i, v) in elements.iter().enumerate() {
+        write!(f, "{}", inner_indent)?;
+        write_value_with(f, v, options, indent_level + 1)?;
+        let is_last = i + 1 == elements.len();
+        let add_comma = !is_last
+            || match options.trailing_comma {
+                TrailingComma::Always => true,
+                TrailingComma::Never => false,
+                TrailingComma::Preserve => array.trailing_comma,
+            };
+        if add_comma {
+            write!(f, ",")?;
+        }
+        writeln!(f)?;
+    }
+    write!(f, "{}", array.trailing)?;
+    write!(f, "{}]{}", outer_indent, array.decor.suffix)
+}
+
+fn write_value_with(
+    f: &mut dyn Write,
+    value: &Value,
+    options: &FormatOptions,
+    indent_level: usize,
+) -> Result {
+    match *value {
+        Value::Array(ref array) => write_array_with(f, array, options, indent_level),
+        ref other => write!(f, "{}", other),
+    }
+}
+
+fn visit_table_with(
+    f: &mut dyn Write,
+    table: &Table,
+    path: &[&str],
+    is_array_of_tables: bool,
+    options: &FormatOptions,
+) -> Result {
+    if path.is_empty() {
+        // don't print header for the root node
+    } else if should_print_table_header(table, is_array_of_tables) {
+        if is_array_of_tables {
+            write!(f, "{}[[", table.decor.prefix)?;
+            write!(f, "{}", path.join("."))?;
+            writeln!(f, "]]{}", table.decor.suffix)?;
+        } else {
+            write!(f, "{}[", table.decor.prefix)?;
+            write!(f, "{}", path.join("."))?;
+            writeln!(f, "]{}", table.decor.suffix)?;
+        }
+    }
+    let eq = if options.space_around_eq { " = " } else { "=" };
+    for kv in table.items.values() {
+        if let Item::Value(ref value) = kv.value {
+            write!(f, "{}{}", kv.key, eq)?;
+            write_value_with(f, value, options, 0)?;
+            writeln!(f)?;
+        }
+    }
+    Ok(())
+}
+
+impl Document {
+    /// Returns a string representation of the TOML document, rendered
+    /// according to `options` instead of the hardcoded spacing and
+    /// single-line arrays used by [`Display`].
+    pub fn to_string_with(&self, options: &FormatOptions) -> String {
+        let mut string = String::default();
+        let mut path = Vec::new();
+
+        self.as_table()
+            .visit_nested_tables(&mut path, false, &mut |t, path, is_array| {
+                visit_table_with(&mut string, t, path, is_array, options)
+            })
+            // write! to string always succeeds, unless we are out of memory,
+            // in which case we can't do much about it.
+            .unwrap();
+
+        string.push_str(&self.trailing);
+        string
+    }
+}
+
 impl Document {
     /// Returns a string representation of the TOML document, attempting to keep
     /// the table headers in their original order.
     ///
-    /// The best case performance of this function is slightly slower than
-    /// Document.to_string(). If you have lots of tables that are in strange
-    /// orders then it may be significantly slower as it has to walk the tree
-    /// multiple times.
+    /// This walks the tree once and sorts the visited tables by position, so
+    /// it costs slightly more than `Document::to_string()` regardless of how
+    /// scrambled the original table order is.
     pub fn to_string_in_original_order(&self) -> String {
         let mut string = String::default();
         let mut path = Vec::new();
@@ -246,3 +591,234 @@ where
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decor::Decor;
+    use crate::table::{ArrayOfTables, Key};
+
+    fn repr(raw_value: &str) -> Repr {
+        Repr {
+            decor: Decor::default(),
+            raw_value: raw_value.to_string(),
+        }
+    }
+
+    #[test]
+    fn to_string_canonical_escapes_control_characters() {
+        // The raw repr is deliberately bogus; canonical output must come
+        // from `value`, not from it.
+        let value = Value::String(Formatted {
+            value: "line1\nline2\u{0}tab\tend\"quote\"".to_string(),
+            repr: repr("garbage-raw-repr"),
+        });
+        assert_eq!(
+            value.to_string_canonical(),
+            "\"line1\\nline2\\u0000tab\\tend\\\"quote\\\"\""
+        );
+    }
+
+    #[test]
+    fn to_string_canonical_suffixes_integral_floats() {
+        let value = Value::Float(Formatted {
+            value: 1.0,
+            repr: repr("1"),
+        });
+        assert_eq!(value.to_string_canonical(), "1.0");
+    }
+
+    #[test]
+    fn to_string_canonical_leaves_fractional_floats_alone() {
+        let value = Value::Float(Formatted {
+            value: 3.5,
+            repr: repr("bogus"),
+        });
+        assert_eq!(value.to_string_canonical(), "3.5");
+    }
+
+    #[test]
+    fn document_to_string_canonical_rerenders_table_values() {
+        let mut table = Table::new();
+        table.insert(
+            Key::new("n"),
+            Item::Value(Value::Float(Formatted {
+                value: 2.0,
+                repr: repr("2"),
+            })),
+        );
+        let document = Document::from_table(table);
+        assert_eq!(document.to_string_canonical(), "n=2.0\n");
+    }
+
+    #[test]
+    fn empty_array_of_tables_entries_keep_their_headers() {
+        // There's no parser in this crate slice, so this builds the
+        // structure that parsing `[[x]]\n[[x]]` would produce directly and
+        // checks it serializes back unchanged.
+        let mut root = Table::new();
+        let mut array = ArrayOfTables::new();
+
+        let mut first = Table::new();
+        first.position = Some(0);
+        array.push(first);
+
+        let mut second = Table::new();
+        second.position = Some(1);
+        array.push(second);
+
+        root.insert(Key::new("x"), Item::ArrayOfTables(array));
+
+        let document = Document::from_table(root);
+        assert_eq!(document.to_string(), "[[x]]\n[[x]]\n");
+        assert_eq!(document.to_string_in_original_order(), "[[x]]\n[[x]]\n");
+    }
+
+    #[test]
+    fn to_string_in_original_order_handles_out_of_order_and_none_positions() {
+        let mut b = Table::new();
+        b.position = Some(1);
+
+        let mut nested_none = Table::new();
+        // Left as `None`: added programmatically, it should inherit `b`'s
+        // anchor position and stay adjacent to it, even though DFS visits
+        // it after `b`.
+        nested_none.position = None;
+        b.insert(Key::new("nested_none"), Item::Table(nested_none));
+
+        let mut a = Table::new();
+        a.position = Some(0);
+
+        let mut root = Table::new();
+        // Inserted (and therefore DFS-visited) as `b` then `a`, even though
+        // `a`'s position (0) comes before `b`'s (1) — the sort must still
+        // put `a` first.
+        root.insert(Key::new("b"), Item::Table(b));
+        root.insert(Key::new("a"), Item::Table(a));
+
+        let document = Document::from_table(root);
+        assert_eq!(
+            document.to_string_in_original_order(),
+            "[a]\n[b]\n[b.nested_none]\n"
+        );
+    }
+
+    fn int_array(elements: &[i64]) -> Array {
+        let mut array = Array::new();
+        for &n in elements {
+            array.push(Value::Integer(Formatted {
+                value: n,
+                repr: repr(&n.to_string()),
+            }));
+        }
+        array
+    }
+
+    fn table_with_array(array: Array) -> Table {
+        let mut table = Table::new();
+        table.insert(Key::new("arr"), Item::Value(Value::Array(array)));
+        table
+    }
+
+    #[test]
+    fn to_string_with_wraps_array_past_element_threshold() {
+        let options = FormatOptions {
+            array_wrap_threshold: Some(2),
+            ..FormatOptions::default()
+        };
+        let document = Document::from_table(table_with_array(int_array(&[1, 2, 3])));
+        assert_eq!(
+            document.to_string_with(&options),
+            "arr=[\n    1,\n    2,\n    3\n]\n"
+        );
+    }
+
+    #[test]
+    fn to_string_with_wraps_array_past_column_budget() {
+        let options = FormatOptions {
+            array_wrap_column_budget: Some(5),
+            ..FormatOptions::default()
+        };
+        // The inline rendering "[1,2,3]" is 7 columns, past the budget.
+        let document = Document::from_table(table_with_array(int_array(&[1, 2, 3])));
+        assert_eq!(
+            document.to_string_with(&options),
+            "arr=[\n    1,\n    2,\n    3\n]\n"
+        );
+    }
+
+    #[test]
+    fn to_string_with_keeps_short_arrays_inline() {
+        let options = FormatOptions {
+            array_wrap_threshold: Some(10),
+            array_wrap_column_budget: Some(100),
+            ..FormatOptions::default()
+        };
+        let document = Document::from_table(table_with_array(int_array(&[1, 2, 3])));
+        assert_eq!(document.to_string_with(&options), "arr=[1,2,3]\n");
+    }
+
+    #[test]
+    fn to_string_with_trailing_comma_always_adds_comma_when_wrapped() {
+        let options = FormatOptions {
+            array_wrap_threshold: Some(0),
+            trailing_comma: TrailingComma::Always,
+            ..FormatOptions::default()
+        };
+        let document = Document::from_table(table_with_array(int_array(&[1, 2, 3])));
+        assert_eq!(
+            document.to_string_with(&options),
+            "arr=[\n    1,\n    2,\n    3,\n]\n"
+        );
+    }
+
+    #[test]
+    fn to_string_with_trailing_comma_never_drops_comma_when_wrapped() {
+        let mut array = int_array(&[1, 2, 3]);
+        array.trailing_comma = true;
+        let options = FormatOptions {
+            array_wrap_threshold: Some(0),
+            trailing_comma: TrailingComma::Never,
+            ..FormatOptions::default()
+        };
+        let document = Document::from_table(table_with_array(array));
+        assert_eq!(
+            document.to_string_with(&options),
+            "arr=[\n    1,\n    2,\n    3\n]\n"
+        );
+    }
+
+    #[test]
+    fn to_string_with_trailing_comma_preserve_keeps_source_comma_when_wrapped() {
+        let mut array = int_array(&[1, 2, 3]);
+        array.trailing_comma = true;
+        let options = FormatOptions {
+            array_wrap_threshold: Some(0),
+            trailing_comma: TrailingComma::Preserve,
+            ..FormatOptions::default()
+        };
+        let document = Document::from_table(table_with_array(array));
+        assert_eq!(
+            document.to_string_with(&options),
+            "arr=[\n    1,\n    2,\n    3,\n]\n"
+        );
+    }
+
+    #[test]
+    fn to_string_with_space_around_eq() {
+        let mut table = Table::new();
+        table.insert(
+            Key::new("n"),
+            Item::Value(Value::Integer(Formatted {
+                value: 1,
+                repr: repr("1"),
+            })),
+        );
+        let options = FormatOptions {
+            space_around_eq: true,
+            ..FormatOptions::default()
+        };
+        let document = Document::from_table(table);
+        assert_eq!(document.to_string_with(&options), "n = 1\n");
+    }
+}
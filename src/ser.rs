@@ -0,0 +1,857 @@
+//! A serde `Serializer` that emits directly into the `Document`/`Table`
+//! model, instead of requiring callers to build a `Value` tree by hand.
+
+use crate::decor::{Decor, Formatted, Repr};
+use crate::display::{escape_string_canonical, format_float_canonical};
+use crate::document::Document;
+use crate::table::{ArrayOfTables, Item, Key, Table};
+use crate::value::{Array, InlineTable, Value};
+use serde::ser::{self, Serialize};
+use std::fmt;
+
+/// Serializes a value implementing [`Serialize`] into a [`Document`].
+pub fn to_document<T>(value: &T) -> Result<Document, Error>
+where
+    T: Serialize,
+{
+    match value.serialize(Serializer)? {
+        Item::Table(table) => Ok(Document::from_table(table)),
+        item => Err(Error::new(format!(
+            "top-level value must serialize to a table, got {}",
+            item.kind_name()
+        ))),
+    }
+}
+
+/// Serializes a value implementing [`Serialize`] into a TOML string.
+pub fn to_string<T>(value: &T) -> Result<String, Error>
+where
+    T: Serialize,
+{
+    Ok(to_document(value)?.to_string())
+}
+
+/// The error type returned by this module's serializer.
+#[derive(Debug)]
+pub struct Error(String);
+
+impl Error {
+    fn new(msg: impl fmt::Display) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Error::new(msg)
+    }
+}
+
+impl Item {
+    fn kind_name(&self) -> &'static str {
+        match self {
+            Item::Value(_) => "value",
+            Item::Table(_) => "table",
+            Item::ArrayOfTables(_) => "array of tables",
+        }
+    }
+
+    /// Converts this item into a [`Value`], turning a `Table` into an
+    /// [`InlineTable`] and an `ArrayOfTables` into an [`Array`] of inline
+    /// tables.
+    fn into_value(self) -> Value {
+        match self {
+            Item::Value(value) => value,
+            Item::Table(table) => Value::InlineTable(table.into_inline_table()),
+            Item::ArrayOfTables(array) => {
+                let mut out = Array::new();
+                for table in array.into_iter() {
+                    out.push(Value::InlineTable(table.into_inline_table()));
+                }
+                Value::Array(out)
+            }
+        }
+    }
+}
+
+fn formatted<T>(value: T, raw_value: String) -> Formatted<T> {
+    Formatted {
+        value,
+        repr: Repr {
+            decor: Decor::default(),
+            raw_value,
+        },
+    }
+}
+
+fn integer_value(v: i64) -> Value {
+    Value::Integer(formatted(v, v.to_string()))
+}
+
+fn float_value(v: f64) -> Value {
+    Value::Float(formatted(v, format_float_canonical(v)))
+}
+
+fn bool_value(v: bool) -> Value {
+    Value::Boolean(formatted(v, v.to_string()))
+}
+
+fn string_value(v: &str) -> Value {
+    Value::String(formatted(v.to_string(), escape_string_canonical(v)))
+}
+
+/// Serializes a value into an [`Item`]: structs and maps become
+/// [`Item::Table`], sequences of structs/maps become
+/// [`Item::ArrayOfTables`], every other sequence becomes an
+/// [`Item::Value`] holding an [`Array`], and scalars become an
+/// [`Item::Value`] holding a freshly-built [`Formatted`] value.
+#[derive(Clone, Copy)]
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Item;
+    type Error = Error;
+
+    type SerializeSeq = SerializeArrayOrArrayOfTables;
+    type SerializeTuple = SerializeArrayOrArrayOfTables;
+    type SerializeTupleStruct = SerializeArrayOrArrayOfTables;
+    type SerializeTupleVariant = SerializeArrayOrArrayOfTables;
+    type SerializeMap = SerializeTable;
+    type SerializeStruct = SerializeTable;
+    type SerializeStructVariant = SerializeTable;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(Item::Value(bool_value(v)))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(Item::Value(integer_value(v)))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        if v > i64::MAX as u64 {
+            return Err(Error::new("u64 value out of range for TOML integer"));
+        }
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(Item::Value(float_value(v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(&v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Item::Value(string_value(v)))
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(Error::new("byte arrays are not supported by TOML"))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::new("unrepresentable None value in TOML"))
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::new("unrepresentable unit value in TOML"))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut table = Table::new();
+        table.insert(Key::new(variant), value.serialize(self)?);
+        Ok(Item::Table(table))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SerializeArrayOrArrayOfTables {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+            variant: None,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        let mut inner = self.serialize_seq(Some(len))?;
+        inner.variant = Some(variant);
+        Ok(inner)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(SerializeTable {
+            table: Table::new(),
+            variant: None,
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(SerializeTable {
+            table: Table::new(),
+            variant: None,
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(SerializeTable {
+            table: Table::new(),
+            variant: Some(variant),
+            next_key: None,
+        })
+    }
+}
+
+struct SerializeArrayOrArrayOfTables {
+    items: Vec<Item>,
+    variant: Option<&'static str>,
+}
+
+impl SerializeArrayOrArrayOfTables {
+    fn finish(self) -> Result<Item, Error> {
+        let is_array_of_tables =
+            !self.items.is_empty() && self.items.iter().all(|item| matches!(item, Item::Table(_)));
+
+        let item = if is_array_of_tables {
+            let mut array = ArrayOfTables::new();
+            for item in self.items {
+                match item {
+                    Item::Table(table) => array.push(table),
+                    _ => unreachable!("checked above"),
+                }
+            }
+            Item::ArrayOfTables(array)
+        } else {
+            let mut array = Array::new();
+            for item in self.items {
+                array.push(item.into_value());
+            }
+            Item::Value(Value::Array(array))
+        };
+
+        match self.variant {
+            Some(variant) => {
+                let mut table = Table::new();
+                table.insert(Key::new(variant), item);
+                Ok(Item::Table(table))
+            }
+            None => Ok(item),
+        }
+    }
+}
+
+impl ser::SerializeSeq for SerializeArrayOrArrayOfTables {
+    type Ok = Item;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.items.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeArrayOrArrayOfTables {
+    type Ok = Item;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArrayOrArrayOfTables {
+    type Ok = Item;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeArrayOrArrayOfTables {
+    type Ok = Item;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+struct SerializeTable {
+    table: Table,
+    variant: Option<&'static str>,
+    next_key: Option<Key>,
+}
+
+impl SerializeTable {
+    fn finish(mut self) -> Result<Item, Error> {
+        set_table_positions(&mut self.table);
+        match self.variant {
+            Some(variant) => {
+                let mut outer = Table::new();
+                outer.insert(Key::new(variant), Item::Table(self.table));
+                Ok(Item::Table(outer))
+            }
+            None => Ok(Item::Table(self.table)),
+        }
+    }
+}
+
+/// Assigns `position` to every nested table in visitation order, so the
+/// result renders cleanly through `visit_nested_tables`.
+fn set_table_positions(table: &mut Table) {
+    let mut next_position = 0;
+    set_table_positions_inner(table, &mut next_position);
+}
+
+fn set_table_positions_inner(table: &mut Table, next_position: &mut usize) {
+    for item in table.items.values_mut() {
+        match &mut item.value {
+            Item::Table(nested) => {
+                nested.position = Some(*next_position);
+                *next_position += 1;
+                set_table_positions_inner(nested, next_position);
+            }
+            Item::ArrayOfTables(array) => {
+                for nested in array.iter_mut() {
+                    nested.position = Some(*next_position);
+                    *next_position += 1;
+                    set_table_positions_inner(nested, next_position);
+                }
+            }
+            Item::Value(_) => {}
+        }
+    }
+}
+
+impl ser::SerializeMap for SerializeTable {
+    type Ok = Item;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = key.serialize(KeySerializer)?;
+        self.next_key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self
+            .next_key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.table.insert(key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeTable {
+    type Ok = Item;
+    type Error = Error;
+
+    fn serialize_field<T>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.table.insert(Key::new(key), value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeTable {
+    type Ok = Item;
+    type Error = Error;
+
+    fn serialize_field<T>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.table.insert(Key::new(key), value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+/// Serializes map keys, which TOML requires to be strings. Struct field
+/// names are used as keys directly and do not pass through here.
+struct KeySerializer;
+
+macro_rules! key_serializer_unsupported {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method(self, _v: $ty) -> Result<Self::Ok, Self::Error> {
+                Err(Error::new("TOML keys must be strings"))
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = Key;
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<Key, Error>;
+    type SerializeTuple = ser::Impossible<Key, Error>;
+    type SerializeTupleStruct = ser::Impossible<Key, Error>;
+    type SerializeTupleVariant = ser::Impossible<Key, Error>;
+    type SerializeMap = ser::Impossible<Key, Error>;
+    type SerializeStruct = ser::Impossible<Key, Error>;
+    type SerializeStructVariant = ser::Impossible<Key, Error>;
+
+    key_serializer_unsupported! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_bytes(&[u8]),
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Key::new(v))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::new("TOML keys must be strings"))
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::new("TOML keys must be strings"))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(Error::new("TOML keys must be strings"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Error::new("TOML keys must be strings"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(Error::new("TOML keys must be strings"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Error::new("TOML keys must be strings"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error::new("TOML keys must be strings"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(Error::new("TOML keys must be strings"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(Error::new("TOML keys must be strings"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error::new("TOML keys must be strings"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[derive(serde::Serialize)]
+    struct Package {
+        name: String,
+        version: String,
+    }
+
+    #[test]
+    fn struct_serializes_to_table() {
+        let package = Package {
+            name: "toml".to_string(),
+            version: "1.0.0".to_string(),
+        };
+        let document = to_document(&package).unwrap();
+        assert_eq!(document.to_string(), "name=\"toml\"\nversion=\"1.0.0\"\n");
+    }
+
+    #[test]
+    fn map_serializes_to_table() {
+        let mut map = BTreeMap::new();
+        map.insert("a".to_string(), 1i64);
+        map.insert("b".to_string(), 2i64);
+        let document = to_document(&map).unwrap();
+        assert_eq!(document.to_string(), "a=1\nb=2\n");
+    }
+
+    #[derive(serde::Serialize)]
+    struct Manifest {
+        keywords: Vec<String>,
+    }
+
+    #[test]
+    fn scalar_seq_serializes_to_array() {
+        let manifest = Manifest {
+            keywords: vec!["a".to_string(), "b".to_string()],
+        };
+        let document = to_document(&manifest).unwrap();
+        assert_eq!(document.to_string(), "keywords=[\"a\",\"b\"]\n");
+    }
+
+    #[derive(serde::Serialize)]
+    struct Dependency {
+        name: String,
+    }
+
+    #[derive(serde::Serialize)]
+    struct ManifestWithDeps {
+        dependencies: Vec<Dependency>,
+    }
+
+    #[test]
+    fn struct_seq_serializes_to_array_of_tables() {
+        let manifest = ManifestWithDeps {
+            dependencies: vec![
+                Dependency {
+                    name: "a".to_string(),
+                },
+                Dependency {
+                    name: "b".to_string(),
+                },
+            ],
+        };
+        let document = to_document(&manifest).unwrap();
+        assert_eq!(
+            document.to_string(),
+            "[[dependencies]]\nname=\"a\"\n[[dependencies]]\nname=\"b\"\n"
+        );
+    }
+
+    #[derive(serde::Serialize)]
+    struct Inner {
+        x: i64,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Outer {
+        first: Inner,
+        second: Inner,
+    }
+
+    #[test]
+    fn set_table_positions_orders_by_visitation() {
+        let outer = Outer {
+            first: Inner { x: 1 },
+            second: Inner { x: 2 },
+        };
+        let document = to_document(&outer).unwrap();
+        let positions: Vec<_> = document
+            .as_table()
+            .items
+            .values()
+            .filter_map(|kv| match &kv.value {
+                Item::Table(t) => t.position,
+                _ => None,
+            })
+            .collect();
+        assert_eq!(positions, vec![Some(0), Some(1)]);
+    }
+
+    #[derive(serde::Serialize)]
+    enum Shape {
+        Unit,
+        Newtype(i64),
+        Tuple(i64, i64),
+        Struct { x: i64, y: i64 },
+    }
+
+    #[derive(serde::Serialize)]
+    struct Wrapper<T> {
+        shape: T,
+    }
+
+    #[test]
+    fn unit_variant_serializes_to_its_name() {
+        let document = to_document(&Wrapper { shape: Shape::Unit }).unwrap();
+        assert_eq!(document.to_string(), "shape=\"Unit\"\n");
+    }
+
+    #[test]
+    fn newtype_variant_wraps_value_in_single_key_table() {
+        let document = to_document(&Wrapper {
+            shape: Shape::Newtype(5),
+        })
+        .unwrap();
+        assert_eq!(document.to_string(), "[shape]\nNewtype=5\n");
+    }
+
+    #[test]
+    fn tuple_variant_wraps_seq_in_single_key_table() {
+        let document = to_document(&Wrapper {
+            shape: Shape::Tuple(1, 2),
+        })
+        .unwrap();
+        assert_eq!(document.to_string(), "[shape]\nTuple=[1,2]\n");
+    }
+
+    #[test]
+    fn struct_variant_wraps_fields_in_named_table() {
+        let document = to_document(&Wrapper {
+            shape: Shape::Struct { x: 1, y: 2 },
+        })
+        .unwrap();
+        assert_eq!(document.to_string(), "[shape]\n[shape.Struct]\nx=1\ny=2\n");
+    }
+
+    #[test]
+    fn none_value_is_unsupported() {
+        #[derive(serde::Serialize)]
+        struct WithOption {
+            value: Option<i64>,
+        }
+
+        let err = to_document(&WithOption { value: None }).unwrap_err();
+        assert!(err.to_string().contains("None"));
+    }
+
+    #[test]
+    fn byte_arrays_are_unsupported() {
+        use serde::Serializer as _;
+
+        let err = Serializer.serialize_bytes(b"abc").unwrap_err();
+        assert!(err.to_string().contains("byte"));
+    }
+
+    #[test]
+    fn non_string_map_keys_are_unsupported() {
+        let mut map = BTreeMap::new();
+        map.insert(1i32, "a".to_string());
+        let err = to_document(&map).unwrap_err();
+        assert!(err.to_string().contains("string"));
+    }
+}